@@ -3,6 +3,39 @@ use bindgen::EnumVariation;
 use std::env;
 use std::path::PathBuf;
 
+/// The single source of truth for whether MKL's 64-bit (ILP64) data model is
+/// in use. Setting `MKL_INT_MODEL=auto` derives it from the target's pointer
+/// width instead of requiring the `ilp64` cargo feature to be set by hand.
+/// Every place that used to branch on `cfg!(feature = "ilp64")` directly --
+/// the `-DMKL_ILP64` cflag, the interface library name, and
+/// `Callbacks::int_macro` -- now goes through this function so they can
+/// never disagree about which `MKL_INT` width was linked.
+///
+/// Panics if ILP64 is requested on a 32-bit (`ia32`) target: MKL only ships
+/// a 32-bit (LP64) interface library there. This lives here rather than in
+/// `interface_lib_name` so it fires unconditionally -- including under the
+/// `sdl` feature, which skips `interface_lib_name` entirely but still emits
+/// `-DMKL_ILP64` via `get_cflags_*`.
+fn use_ilp64() -> bool {
+    let ilp64 = if env::var("MKL_INT_MODEL").as_deref() == Ok("auto") {
+        // `CARGO_CFG_TARGET_POINTER_WIDTH` reflects the *target*'s pointer
+        // width; `cfg!(target_pointer_width = ..)` would instead reflect the
+        // host build script binary, which is wrong when cross-compiling.
+        env::var("CARGO_CFG_TARGET_POINTER_WIDTH").as_deref() == Ok("64")
+    } else {
+        cfg!(feature = "ilp64")
+    };
+
+    if ilp64 && cfg!(target_arch = "x86") {
+        panic!(
+            "ILP64 is not supported on 32-bit (ia32) targets: \
+             MKL only provides a 32-bit (LP64) interface library there."
+        );
+    }
+
+    ilp64
+}
+
 /// Paths required for linking to MKL
 struct InstallationDirectories {
     mkl_lib_dir: String,
@@ -26,8 +59,11 @@ impl InstallationDirectories {
     ///
     /// Checks if paths exist.
     fn try_from_root(root: &str) -> Result<Self, String> {
-        // TODO determine if we need to support ia32 as well
-        let itype = "intel64";
+        let itype = if cfg!(target_arch = "x86") {
+            "ia32"
+        } else {
+            "intel64"
+        };
 
         let tbb_lib_subdir = if cfg!(target_os = "linux") {
             format!("/{}/gcc4.8", itype)
@@ -63,6 +99,161 @@ impl InstallationDirectories {
         Self::try_custom(&mkl_lib_dir, &include_dir, &tbb_lib_dir, &omp_lib_dir)
     }
 
+    /// Discovers MKL through `pkg-config` and links it directly.
+    ///
+    /// Recent MKL distributions ship one `.pc` file per
+    /// interface/threading/linkage combination, e.g. `mkl-dynamic-lp64-seq`
+    /// or `mkl-sdl-ilp64`. The name is selected from [`use_ilp64`], the
+    /// active `openmp`, `tbb` and `sdl` features, and the
+    /// `MKL_PKG_CONFIG_STATIC`/`MKL_PKG_CONFIG_DYNAMIC` overrides;
+    /// `pkg-config` is invoked for it, and the resulting lib paths and libs
+    /// are emitted straight away rather than routed through
+    /// [`InstallationDirectories`]. Returns the clang args bindgen needs: the
+    /// `-I` flags plus any other cflags (e.g. `-DMKL_ILP64`) from the `.pc`.
+    fn try_pkg_config() -> Result<Vec<String>, String> {
+        let cross_compiling = env::var("TARGET").ok() != env::var("HOST").ok();
+        if cross_compiling && env::var_os("PKG_CONFIG_ALLOW_CROSS").is_none() {
+            return Err(
+                "refusing to probe the host's pkg-config database while cross-compiling; \
+                 set PKG_CONFIG_ALLOW_CROSS=1 to override"
+                    .into(),
+            );
+        }
+
+        let interface = if use_ilp64() { "ilp64" } else { "lp64" };
+
+        let pc_name = if cfg!(feature = "sdl") {
+            format!("mkl-sdl-{interface}")
+        } else {
+            let prefer_static = env::var_os("MKL_PKG_CONFIG_STATIC").is_some()
+                && env::var_os("MKL_PKG_CONFIG_DYNAMIC").is_none();
+            let linkage = if prefer_static { "static" } else { "dynamic" };
+            let threading = if cfg!(feature = "openmp") {
+                "iomp"
+            } else if cfg!(feature = "tbb") {
+                "tbb"
+            } else {
+                "seq"
+            };
+            format!("mkl-{linkage}-{interface}-{threading}")
+        };
+
+        let output = std::process::Command::new("pkg-config")
+            .args(["--cflags", "--libs", &pc_name])
+            .output()
+            .map_err(|err| format!("failed to run pkg-config: {err}"))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "pkg-config could not find '{pc_name}': {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        if cfg!(feature = "cluster") {
+            println!(
+                "cargo:warning=The 'cluster' feature is not yet wired into pkg-config discovery; \
+                 ScaLAPACK/BLACS will not be linked. Set MKL_LIB_DIR/MKL_INCLUDE_DIR or ONEAPI_ROOT \
+                 instead if you need the cluster feature."
+            );
+        }
+
+        let mut clang_args = Vec::new();
+        for token in String::from_utf8_lossy(&output.stdout).split_whitespace() {
+            if let Some(path) = token.strip_prefix("-I") {
+                clang_args.push("-I".to_string());
+                clang_args.push(path.to_string());
+            } else if let Some(path) = token.strip_prefix("-L") {
+                println!("cargo:rustc-link-search=native={}", path);
+            } else if let Some(lib) = token.strip_prefix("-l") {
+                println!("cargo:rustc-link-lib={}", lib);
+            } else {
+                // Forward anything else (-D defines, -m64, etc.) straight to
+                // bindgen so e.g. the ilp64 .pc variants' -DMKL_ILP64 isn't
+                // silently dropped and out of sync with the linked interface.
+                clang_args.push(token.to_string());
+            }
+        }
+
+        Ok(clang_args)
+    }
+
+    /// Finds an MKL installation without requiring `setvars` to have been
+    /// sourced: reads the Windows registry, or scans well-known Unix install
+    /// roots for the newest `mkl/<version>` directory.
+    fn try_auto_discover() -> Result<Self, String> {
+        let candidate_roots: Vec<PathBuf> = if cfg!(target_os = "windows") {
+            vec![PathBuf::from(find_windows_oneapi_root()?)]
+        } else {
+            let mut roots = vec![
+                PathBuf::from("/opt/intel/oneapi"),
+                PathBuf::from("/opt/intel/mkl"),
+            ];
+            if let Ok(home) = env::var("HOME") {
+                roots.push(PathBuf::from(home).join("intel/oneapi"));
+            }
+            roots
+        };
+
+        // Same arch/OS subdirectory convention as `try_from_root`.
+        let itype = if cfg!(target_arch = "x86") {
+            "ia32"
+        } else {
+            "intel64"
+        };
+        let tbb_lib_subdir = if cfg!(target_os = "linux") {
+            format!("/{itype}/gcc4.8")
+        } else if cfg!(target_os = "windows") {
+            format!("/{itype}/vc14")
+        } else {
+            String::new()
+        };
+
+        for root in &candidate_roots {
+            let Some(mkl_root) = newest_mkl_root(root) else {
+                continue;
+            };
+
+            println!(
+                "cargo:warning=Auto-discovered MKL installation at '{}'",
+                mkl_root.display()
+            );
+
+            // Prefer the classic `lib/<itype>` layout used by standalone MKL
+            // installs, falling back to oneAPI's flat `lib` layout.
+            let legacy_lib_dir = mkl_root.join("lib").join(itype);
+            let mkl_lib_dir = if legacy_lib_dir.exists() {
+                legacy_lib_dir
+            } else {
+                mkl_root.join("lib")
+            };
+            let include_dir = mkl_root.join("include");
+
+            // TBB/OpenMP only matter when their features are enabled, in
+            // which case `try_custom` will warn if these don't exist under
+            // this particular root.
+            let tbb_lib_dir =
+                PathBuf::from(format!("{}/tbb/latest/lib{tbb_lib_subdir}", root.display()));
+            let omp_lib_dir = root.join("compiler/latest/lib");
+
+            return Self::try_custom(
+                &mkl_lib_dir.to_string_lossy(),
+                &include_dir.to_string_lossy(),
+                &tbb_lib_dir.to_string_lossy(),
+                &omp_lib_dir.to_string_lossy(),
+            );
+        }
+
+        Err(format!(
+            "Could not find an MKL installation under any of: {}",
+            candidate_roots
+                .iter()
+                .map(|root| root.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))
+    }
+
     /// Constructs paths required for linking MKL using system paths.
     ///
     /// Checks if paths exist.
@@ -179,6 +370,11 @@ fn get_dynamic_link_libs_windows() -> Vec<String> {
     // Note: The order of the libraries is very important
     let mut libs = Vec::new();
 
+    if cfg!(feature = "sdl") {
+        libs.push("mkl_rt");
+        return libs.into_iter().map(|s| s.into()).collect();
+    }
+
     if cfg!(feature = "tbb") {
         libs.push("tbb");
     }
@@ -190,6 +386,11 @@ fn get_dynamic_link_libs_linux() -> Vec<String> {
     // Note: The order of the libraries is very important
     let mut libs = Vec::new();
 
+    if cfg!(feature = "sdl") {
+        libs.push("mkl_rt");
+        return libs.into_iter().map(|s| s.into()).collect();
+    }
+
     if cfg!(feature = "openmp") {
         libs.push("iomp5");
     } else if cfg!(feature = "tbb") {
@@ -206,6 +407,11 @@ fn get_dynamic_link_libs_macos() -> Vec<String> {
     // Note: The order of the libraries is very important
     let mut libs = Vec::new();
 
+    if cfg!(feature = "sdl") {
+        libs.push("mkl_rt");
+        return libs.into_iter().map(|s| s.into()).collect();
+    }
+
     if cfg!(feature = "openmp") {
         libs.push("iomp5");
     }
@@ -214,76 +420,144 @@ fn get_dynamic_link_libs_macos() -> Vec<String> {
     libs.into_iter().map(|s| s.into()).collect()
 }
 
-fn get_static_link_libs_windows() -> Vec<String> {
+/// The ScaLAPACK/BLACS/cluster-DFT libraries needed by the `cluster`
+/// feature. Must be linked ahead of the interface/threading/core layers
+/// since they depend on them.
+///
+/// Only called from the static builders: like the interface/threading/core
+/// layers themselves, the dynamic builders never link these by name (`sdl`
+/// pulls in everything via `mkl_rt`, and the non-`sdl` dynamic path is
+/// otherwise limited to `tbb`/`iomp5` and system libraries), so there is no
+/// dynamic counterpart
+/// to wire this into.
+fn get_cluster_link_libs() -> Vec<String> {
     let mut libs = Vec::new();
 
-    if cfg!(feature = "ilp64") {
-        libs.push("mkl_intel_ilp64");
-    } else {
-        libs.push("mkl_intel_lp64");
+    if !cfg!(feature = "cluster") {
+        return libs;
+    }
+
+    let suffix = if use_ilp64() { "ilp64" } else { "lp64" };
+
+    libs.push(format!("mkl_scalapack_{suffix}"));
+    libs.push("mkl_cdft_core".to_string());
+
+    let mpi = env::var("MKL_BLACS_MPI").unwrap_or_else(|_| "intelmpi".to_string());
+    let blacs_mpi = match mpi.as_str() {
+        "openmpi" => "openmpi",
+        // MPICH is ABI-compatible with Intel MPI's BLACS wrapper.
+        "intelmpi" | "mpich" => "intelmpi",
+        other => {
+            println!(
+                "cargo:warning=Unrecognized MKL_BLACS_MPI value '{other}', defaulting to 'intelmpi'"
+            );
+            "intelmpi"
+        }
     };
+    libs.push(format!("mkl_blacs_{blacs_mpi}_{suffix}"));
+
+    libs
+}
+
+/// The name of MKL's interface library for the current target, e.g.
+/// `mkl_intel_lp64`/`mkl_intel_ilp64` on 64-bit, or the 32-bit-only
+/// `mkl_intel_c` (Windows) / `mkl_intel` (Unix) on `ia32`.
+///
+/// `use_ilp64()` panics before returning `true` on a 32-bit target, so by
+/// the time we get here ILP64 + ia32 has already been rejected.
+fn interface_lib_name(is_windows: bool) -> String {
+    let ilp64 = use_ilp64();
+    if cfg!(target_arch = "x86") {
+        if is_windows {
+            "mkl_intel_c".to_string()
+        } else {
+            "mkl_intel".to_string()
+        }
+    } else if ilp64 {
+        "mkl_intel_ilp64".to_string()
+    } else {
+        "mkl_intel_lp64".to_string()
+    }
+}
+
+fn get_static_link_libs_windows() -> Vec<String> {
+    // In single dynamic library mode the interface/threading/core layers are
+    // resolved at runtime by `mkl_rt`, so there is nothing to statically link.
+    if cfg!(feature = "sdl") {
+        return Vec::new();
+    }
+
+    let mut libs = get_cluster_link_libs();
+
+    libs.push(interface_lib_name(true));
 
     if cfg!(feature = "openmp") {
-        libs.push("mkl_intel_thread");
+        libs.push("mkl_intel_thread".to_string());
     } else if cfg!(feature = "tbb") {
-        libs.push("mkl_tbb_thread");
+        libs.push("mkl_tbb_thread".to_string());
     } else {
-        libs.push("mkl_sequential");
+        libs.push("mkl_sequential".to_string());
     };
 
-    libs.push("mkl_core");
+    libs.push("mkl_core".to_string());
 
     if cfg!(feature = "openmp") {
-        libs.push("libiomp5md");
+        libs.push("libiomp5md".to_string());
     }
 
-    libs.into_iter().map(|s| s.into()).collect()
+    libs
 }
 
 fn get_static_link_libs_macos() -> Vec<String> {
-    // Note: The order of the libraries is very important
-    let mut libs = Vec::new();
+    // In single dynamic library mode the interface/threading/core layers are
+    // resolved at runtime by `mkl_rt`, so there is nothing to statically link.
+    if cfg!(feature = "sdl") {
+        return Vec::new();
+    }
 
-    if cfg!(feature = "ilp64") {
-        libs.push("mkl_intel_ilp64");
-    } else {
-        libs.push("mkl_intel_lp64");
-    };
+    if cfg!(feature = "cluster") {
+        println!("cargo:warning=The 'cluster' feature is not supported on macOS; MKL does not ship ScaLAPACK/BLACS for this platform.");
+    }
+
+    // Note: The order of the libraries is very important
+    let mut libs = vec![interface_lib_name(false)];
 
     if cfg!(feature = "openmp") {
-        libs.push("mkl_intel_thread");
+        libs.push("mkl_intel_thread".to_string());
     } else if cfg!(feature = "tbb") {
-        libs.push("mkl_tbb_thread");
+        libs.push("mkl_tbb_thread".to_string());
     } else {
-        libs.push("mkl_sequential");
+        libs.push("mkl_sequential".to_string());
     };
 
-    libs.push("mkl_core");
+    libs.push("mkl_core".to_string());
 
-    libs.into_iter().map(|s| s.into()).collect()
+    libs
 }
 
 fn get_static_link_libs_linux() -> Vec<String> {
+    // In single dynamic library mode the interface/threading/core layers are
+    // resolved at runtime by `mkl_rt`, so there is nothing to statically link.
+    if cfg!(feature = "sdl") {
+        return Vec::new();
+    }
+
     // Note: The order of the libraries is very important
-    let mut libs = Vec::new();
+    let mut libs = get_cluster_link_libs();
 
-    if cfg!(feature = "ilp64") {
-        libs.push("mkl_intel_ilp64");
-    } else {
-        libs.push("mkl_intel_lp64");
-    };
+    libs.push(interface_lib_name(false));
 
     if cfg!(feature = "openmp") {
-        libs.push("mkl_intel_thread");
+        libs.push("mkl_intel_thread".to_string());
     } else if cfg!(feature = "tbb") {
-        libs.push("mkl_tbb_thread");
+        libs.push("mkl_tbb_thread".to_string());
     } else {
-        libs.push("mkl_sequential");
+        libs.push("mkl_sequential".to_string());
     };
 
-    libs.push("mkl_core");
+    libs.push("mkl_core".to_string());
 
-    libs.into_iter().map(|s| s.into()).collect()
+    libs
 }
 
 fn get_dynamic_link_libs() -> Vec<String> {
@@ -313,7 +587,7 @@ fn get_static_link_libs() -> Vec<String> {
 fn get_cflags_windows(install_dirs: Option<&InstallationDirectories>) -> Vec<String> {
     let mut cflags = Vec::new();
 
-    if cfg!(feature = "ilp64") {
+    if use_ilp64() {
         cflags.push("-DMKL_ILP64".into());
     }
 
@@ -327,7 +601,7 @@ fn get_cflags_windows(install_dirs: Option<&InstallationDirectories>) -> Vec<Str
 fn get_cflags_linux(install_dirs: Option<&InstallationDirectories>) -> Vec<String> {
     let mut cflags = Vec::new();
 
-    if cfg!(feature = "ilp64") {
+    if use_ilp64() {
         cflags.push("-DMKL_ILP64".into());
     }
 
@@ -345,7 +619,7 @@ fn get_cflags_linux(install_dirs: Option<&InstallationDirectories>) -> Vec<Strin
 fn get_cflags_macos(install_dirs: Option<&InstallationDirectories>) -> Vec<String> {
     let mut cflags = Vec::new();
 
-    if cfg!(feature = "ilp64") {
+    if use_ilp64() {
         cflags.push("-DMKL_ILP64".into());
     }
 
@@ -378,7 +652,7 @@ impl ParseCallbacks for Callbacks {
         // MKL expects these constants to be compatible with MKL_INT.
         if &name[..4] == "MKL_" {
             // Important: this should be the same as MKL_INT
-            if cfg!(feature = "ilp64") {
+            if use_ilp64() {
                 Some(IntKind::I64)
             } else {
                 Some(IntKind::I32)
@@ -389,6 +663,198 @@ impl ParseCallbacks for Callbacks {
     }
 }
 
+/// Finds the newest versioned `mkl` directory under `root`, i.e. `root/mkl`
+/// for a oneAPI root like `/opt/intel/oneapi`, or `root` itself for a
+/// standalone MKL root like `/opt/intel/mkl`. Falls back to a `latest`
+/// symlink if no numerically-versioned directory is found.
+fn newest_mkl_root(root: &std::path::Path) -> Option<PathBuf> {
+    let container = if root.file_name().map(|name| name == "mkl").unwrap_or(false) {
+        root.to_path_buf()
+    } else {
+        root.join("mkl")
+    };
+
+    let mut versions: Vec<(Vec<u32>, PathBuf)> = std::fs::read_dir(&container)
+        .ok()?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let name = entry.file_name().into_string().ok()?;
+            let version: Vec<u32> = name
+                .split('.')
+                .map(|part| part.parse())
+                .collect::<Result<_, _>>()
+                .ok()?;
+            Some((version, entry.path()))
+        })
+        .collect();
+
+    versions.sort_by(|a, b| a.0.cmp(&b.0));
+
+    versions.pop().map(|(_, path)| path).or_else(|| {
+        let latest = container.join("latest");
+        latest.exists().then_some(latest)
+    })
+}
+
+/// Reads the oneAPI install root out of the Windows registry via the `reg`
+/// command line tool.
+fn find_windows_oneapi_root() -> Result<String, String> {
+    for key in [
+        r"HKLM\SOFTWARE\Intel\oneAPI",
+        r"HKLM\SOFTWARE\WOW6432Node\Intel\oneAPI",
+    ] {
+        let output = std::process::Command::new("reg")
+            .args(["query", key, "/v", "INSTALLDIR"])
+            .output()
+            .map_err(|err| format!("failed to run 'reg query': {err}"))?;
+
+        if !output.status.success() {
+            continue;
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let install_dir = stdout.lines().find_map(|line| {
+            let rest = line.trim().strip_prefix("INSTALLDIR")?;
+            let value_start = rest.find("REG_SZ")? + "REG_SZ".len();
+            Some(rest[value_start..].trim().to_string())
+        });
+
+        if let Some(install_dir) = install_dir {
+            return Ok(install_dir);
+        }
+    }
+
+    Err("Could not find the oneAPI install root in the Windows registry".into())
+}
+
+/// Locates an MKL installation via [`InstallationDirectories`] and emits the
+/// `cargo:rustc-link-search`/`cargo:rustc-link-lib` directives for it.
+///
+/// Returns the clang args bindgen needs to parse MKL's headers: the include
+/// directory flags plus `-DMKL_ILP64` when ILP64 is in use.
+fn discover_and_link() -> Vec<String> {
+    // First try loading fully custom paths. This serves as a potential override to the "usual" way of installing MKL.
+    // This is also useful when MKL is installed by another package manager like NuGet on windows.
+    let install_dirs = InstallationDirectories::try_custom_env()
+        .map_err(|err| println!("WARNING: {}", err))
+        .ok()
+        .or_else(|| {
+            // Next try using the environment variable for ONEAPI_ROOT.
+            InstallationDirectories::try_from_env_root()
+                .map_err(|err| println!("WARNING: {}", err))
+                .ok()
+        })
+        .or_else(|| {
+            // Next, auto-discover a oneAPI/MKL install without requiring
+            // `setvars` to have been sourced first.
+            InstallationDirectories::try_auto_discover()
+                .map_err(|err| println!("WARNING: {}", err))
+                .ok()
+        })
+        .or_else(|| {
+            // Finally try a system installed version of MKL.
+            InstallationDirectories::try_system()
+                .map_err(|err| println!("WARNING: {}", err))
+                .ok()
+        });
+
+    if let Some(install_dirs) = install_dirs.as_ref() {
+        for lib_dir in get_lib_dirs(install_dirs) {
+            println!("cargo:rustc-link-search=native={}", lib_dir);
+        }
+    }
+
+    for lib in get_static_link_libs() {
+        println!("cargo:rustc-link-lib=static={}", lib);
+    }
+
+    for lib in get_dynamic_link_libs() {
+        println!("cargo:rustc-link-lib={}", lib);
+    }
+
+    get_cflags(install_dirs.as_ref())
+}
+
+/// Extracts the value of a `#define NAME <int>` line for `macro_name` out of
+/// a C header's contents.
+fn extract_define(header: &str, macro_name: &str) -> Option<i64> {
+    header.lines().find_map(|line| {
+        let line = line.trim();
+        let rest = line.strip_prefix("#define")?.trim_start();
+        let rest = rest.strip_prefix(macro_name)?;
+        rest.trim().parse().ok()
+    })
+}
+
+/// Parses `mkl_version.h` (falling back to `mkl.h`) in each of `include_dirs`
+/// for `__INTEL_MKL__`/`__INTEL_MKL_MINOR__`/`__INTEL_MKL_UPDATE__` and
+/// assembles a `major.minor.update` version string.
+fn detect_mkl_version(include_dirs: &[String]) -> Option<String> {
+    for include_dir in include_dirs {
+        for header_name in ["mkl_version.h", "mkl.h"] {
+            let Ok(header) = std::fs::read_to_string(format!("{include_dir}/{header_name}")) else {
+                continue;
+            };
+            let (Some(major), Some(minor), Some(update)) = (
+                extract_define(&header, "__INTEL_MKL__"),
+                extract_define(&header, "__INTEL_MKL_MINOR__"),
+                extract_define(&header, "__INTEL_MKL_UPDATE__"),
+            ) else {
+                // This header didn't have all three macros; fall through to
+                // the next header name / include dir instead of giving up.
+                continue;
+            };
+            return Some(format!("{major}.{minor}.{update}"));
+        }
+    }
+    None
+}
+
+/// Pulls the include directories out of a set of bindgen clang args (the
+/// paths following each `-I`), so version detection can reuse whatever
+/// discovery mechanism produced `clang_args`.
+fn include_dirs_from_clang_args(clang_args: &[String]) -> Vec<String> {
+    clang_args
+        .windows(2)
+        .filter_map(|pair| {
+            (pair[0] == "-I" || pair[0] == "--include-directory").then(|| pair[1].clone())
+        })
+        .collect()
+}
+
+/// Detects the MKL version from `clang_args`' include directories, emits it
+/// as build script metadata, and enforces `MKL_MIN_VERSION` if set.
+fn detect_and_emit_mkl_version(clang_args: &[String]) {
+    let include_dirs = include_dirs_from_clang_args(clang_args);
+    let Some(version) = detect_mkl_version(&include_dirs) else {
+        println!("cargo:warning=Unable to detect the MKL version from mkl_version.h/mkl.h");
+        return;
+    };
+
+    println!("cargo:rustc-env=MKL_VERSION={version}");
+    println!("cargo:version={version}");
+
+    if let Ok(min_version) = env::var("MKL_MIN_VERSION") {
+        let parse = |v: &str| -> Option<(u32, u32, u32)> {
+            let mut parts = v.split('.').map(|part| part.parse().ok());
+            Some((parts.next()??, parts.next()??, parts.next()??))
+        };
+        match (parse(&version), parse(&min_version)) {
+            (Some(found), Some(required)) if found < required => {
+                panic!(
+                    "MKL version {version} is older than the required MKL_MIN_VERSION {min_version}"
+                );
+            }
+            (None, _) | (_, None) => {
+                println!(
+                    "cargo:warning=Could not parse MKL_MIN_VERSION ('{min_version}') or detected version ('{version}') as major.minor.update"
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
 fn main() {
     if cfg!(not(any(
         feature = "all",
@@ -408,43 +874,27 @@ like to generate symbols for all modules."
 
     // Link with the proper MKL libraries and simultaneously set up arguments for bindgen.
     // Otherwise we don't get e.g. the correct MKL preprocessor definitions).
-    let clang_args = {
-        // First try loading fully custom paths. This serves as a potential override to the "usual" way of installing MKL.
-        // This is also useful when MKL is installed by another package manager like NuGet on windows.
-        let install_dirs = InstallationDirectories::try_custom_env()
-            .map_err(|err| println!("WARNING: {}", err))
-            .ok()
-            .or_else(|| {
-                // Next try using the environment variable for ONEAPI_ROOT.
-                InstallationDirectories::try_from_env_root()
-                    .map_err(|err| println!("WARNING: {}", err))
-                    .ok()
-            })
-            .or_else(|| {
-                // Finally try a system installed version of MKL.
-                InstallationDirectories::try_system()
-                    .map_err(|err| println!("WARNING: {}", err))
-                    .ok()
-            });
-
-        if let Some(install_dirs) = install_dirs.as_ref() {
-            for lib_dir in get_lib_dirs(install_dirs) {
-                println!("cargo:rustc-link-search=native={}", lib_dir);
+    let pkg_config_points_at_mkl = env::var("PKG_CONFIG_PATH")
+        .map(|path| path.to_lowercase().contains("mkl"))
+        .unwrap_or(false);
+
+    let clang_args = if pkg_config_points_at_mkl {
+        match InstallationDirectories::try_pkg_config() {
+            Ok(clang_args) => clang_args,
+            Err(err) => {
+                println!(
+                    "cargo:warning=pkg-config discovery failed, falling back: {}",
+                    err
+                );
+                discover_and_link()
             }
         }
-
-        for lib in get_static_link_libs() {
-            println!("cargo:rustc-link-lib=static={}", lib);
-        }
-
-        for lib in get_dynamic_link_libs() {
-            println!("cargo:rustc-link-lib={}", lib);
-        }
-
-        let args = get_cflags(install_dirs.as_ref());
-        args
+    } else {
+        discover_and_link()
     };
 
+    detect_and_emit_mkl_version(&clang_args);
+
     #[allow(unused_mut)]
     let mut builder = bindgen::Builder::default()
         .header("wrapper.h")
@@ -495,6 +945,22 @@ like to generate symbols for all modules."
         {
             builder = builder.allowlist_function("mkl_sparse_.*");
         }
+
+        #[cfg(feature = "cluster")]
+        {
+            let cluster_regex = "(p[sdcz](gemm|gesv).*)|(mkl_cdft_.*)|(CDFT_DM_.*)";
+            builder = builder
+                .allowlist_function(cluster_regex)
+                .allowlist_type(cluster_regex)
+                .allowlist_var(cluster_regex);
+        }
+
+        #[cfg(feature = "sdl")]
+        {
+            builder = builder
+                .allowlist_function("mkl_set_.*_layer")
+                .allowlist_var("MKL_(INTERFACE|THREADING)_.*");
+        }
     }
 
     let bindings = builder.generate().expect("Unable to generate bindings");
@@ -0,0 +1,12 @@
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+
+//! Raw FFI bindings to Intel MKL, generated by `build.rs` via `bindgen`.
+//!
+//! See the crate README for how to point the build at an MKL installation.
+
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+#[cfg(feature = "sdl")]
+pub mod sdl;
@@ -0,0 +1,70 @@
+//! Safe runtime selection of the interface and threading layer when linked
+//! against the single dynamic library (`mkl_rt`, the `sdl` feature).
+//!
+//! Without `sdl` these layers are fixed at link time by the `ilp64` cargo
+//! feature (or `MKL_INT_MODEL=auto`) and the `openmp` and `tbb` cargo
+//! features. With `sdl`, `mkl_rt` defers that
+//! choice to runtime, so it must be made once via [`set_interface_layer`]
+//! and [`set_threading_layer`] before calling into MKL.
+
+use crate::{
+    mkl_set_interface_layer, mkl_set_threading_layer, MKL_INTERFACE_ILP64, MKL_INTERFACE_LP64,
+    MKL_THREADING_GNU, MKL_THREADING_INTEL, MKL_THREADING_SEQUENTIAL, MKL_THREADING_TBB,
+};
+
+/// Data model used to interpret integer arguments passed to MKL routines.
+///
+/// Must match the `MKL_INT` width the rest of the program was compiled
+/// against; picking the wrong one is a silent ABI mismatch, not a link error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InterfaceLayer {
+    /// 32-bit `MKL_INT`.
+    Lp64,
+    /// 64-bit `MKL_INT`.
+    Ilp64,
+}
+
+/// Threading runtime used internally by MKL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadingLayer {
+    /// Single-threaded.
+    Sequential,
+    /// Intel OpenMP (`libiomp5`).
+    Intel,
+    /// GNU OpenMP (`libgomp`).
+    Gnu,
+    /// Threading Building Blocks.
+    Tbb,
+}
+
+/// Selects the interface layer (LP64 vs ILP64) used by `mkl_rt` for the
+/// remainder of the process.
+///
+/// Returns `false` if MKL rejected the requested layer, e.g. because it is
+/// unavailable in the linked `mkl_rt`.
+pub fn set_interface_layer(layer: InterfaceLayer) -> bool {
+    let code = match layer {
+        InterfaceLayer::Lp64 => MKL_INTERFACE_LP64,
+        InterfaceLayer::Ilp64 => MKL_INTERFACE_ILP64,
+    };
+    // SAFETY: `mkl_set_interface_layer` only reads `code`; it performs no
+    // pointer dereferences and is documented as callable at any point before
+    // the first MKL computational routine runs.
+    unsafe { mkl_set_interface_layer(code as i32) != -1 }
+}
+
+/// Selects the threading layer used by `mkl_rt` for the remainder of the
+/// process.
+///
+/// Returns `false` if MKL rejected the requested layer, e.g. because it is
+/// unavailable in the linked `mkl_rt`.
+pub fn set_threading_layer(layer: ThreadingLayer) -> bool {
+    let code = match layer {
+        ThreadingLayer::Sequential => MKL_THREADING_SEQUENTIAL,
+        ThreadingLayer::Intel => MKL_THREADING_INTEL,
+        ThreadingLayer::Gnu => MKL_THREADING_GNU,
+        ThreadingLayer::Tbb => MKL_THREADING_TBB,
+    };
+    // SAFETY: see `set_interface_layer`.
+    unsafe { mkl_set_threading_layer(code as i32) != -1 }
+}